@@ -0,0 +1,94 @@
+//! An owned, strongly-typed representation of an [`Address`] payload.
+
+use soroban_sdk::unwrap::UnwrapOptimized;
+use soroban_sdk::{Address, Bytes, BytesN, Env};
+
+use crate::{AddressPayloadError, AddressPayloadExt, AddressPayloadType};
+
+/// An owned payload decoded from an [`Address`].
+///
+/// Pairs an [`AddressPayloadType`] with its 32-byte payload (and, for muxed account addresses,
+/// the multiplexing id) as a single misuse-resistant value that can be stored in contract state
+/// and pattern-matched on, instead of juggling the loose `(AddressPayloadType, Bytes)` tuple
+/// returned by [`AddressPayloadExt::payload`].
+///
+/// Stellar distinguishes the public and test networks only at the transaction layer, not in the
+/// address itself, so `AddressPayload` carries no network tag.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddressPayload {
+    payload_type: AddressPayloadType,
+    payload: BytesN<32>,
+    muxed_id: Option<u64>,
+}
+
+impl AddressPayload {
+    /// Constructs an [`AddressPayload`] from a payload type and 32-byte array.
+    ///
+    /// `muxed_id` supplies the multiplexing id for
+    /// [`AddressPayloadType::MuxedAccountEd25519`] and is ignored for all other payload types.
+    /// Returns [`AddressPayloadError::MissingMuxedId`] if the muxed payload type is supplied
+    /// without a `muxed_id`.
+    pub fn from_array(
+        env: &Env,
+        payload_type: AddressPayloadType,
+        payload: [u8; 32],
+        muxed_id: Option<u64>,
+    ) -> Result<Self, AddressPayloadError> {
+        if payload_type == AddressPayloadType::MuxedAccountEd25519 && muxed_id.is_none() {
+            return Err(AddressPayloadError::MissingMuxedId);
+        }
+        let muxed_id = if payload_type == AddressPayloadType::MuxedAccountEd25519 {
+            muxed_id
+        } else {
+            None
+        };
+        Ok(Self {
+            payload_type,
+            payload: BytesN::from_array(env, &payload),
+            muxed_id,
+        })
+    }
+
+    /// Returns the payload type.
+    pub fn payload_type(&self) -> AddressPayloadType {
+        self.payload_type
+    }
+
+    /// Returns the 32-byte payload.
+    pub fn payload(&self) -> BytesN<32> {
+        self.payload.clone()
+    }
+
+    /// Returns the multiplexing id, for [`AddressPayloadType::MuxedAccountEd25519`].
+    pub fn muxed_id(&self) -> Option<u64> {
+        self.muxed_id
+    }
+}
+
+impl TryFrom<Address> for AddressPayload {
+    type Error = AddressPayloadError;
+
+    fn try_from(address: Address) -> Result<Self, Self::Error> {
+        let env = address.env().clone();
+        let (payload_type, payload, muxed_id) = address.payload(&env)?;
+        let payload: BytesN<32> = payload
+            .try_into()
+            .map_err(|_| AddressPayloadError::InvalidXdr)?;
+        Ok(Self {
+            payload_type,
+            payload,
+            muxed_id,
+        })
+    }
+}
+
+impl From<AddressPayload> for Address {
+    fn from(value: AddressPayload) -> Self {
+        let env = value.payload.env().clone();
+        let payload = Bytes::from(value.payload);
+        // Well-formed by construction: payload is always 32 bytes, and muxed_id is always
+        // present when payload_type is MuxedAccountEd25519.
+        Address::from_payload(&env, value.payload_type, &payload, value.muxed_id)
+            .unwrap_optimized()
+    }
+}
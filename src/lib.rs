@@ -10,9 +10,20 @@
 //! - **Account addresses** (G...) contain a 32-byte Ed25519 public key that corresponds to the
 //!   account's master key, that depending on the configuration of that account may or may not be a
 //!   signer of the acccount.
+//! - **Muxed account addresses** (M...) contain the same 32-byte Ed25519 public key as an account
+//!   address, plus a 64-bit multiplexing id used to distinguish between users sharing the
+//!   underlying account.
+//! - **Claimable balance addresses** (B...) contain a 32-byte hash that identifies a claimable
+//!   balance entry.
+//! - **Liquidity pool addresses** (L...) contain a 32-byte hash that identifies a liquidity pool.
 //!
 //! This library supports all address types as of Stellar Protocol 24.
 //!
+//! The [`strkey`] module provides a self-contained codec for the `G.../C.../M...` string form
+//! that works without an [`Env`] or XDR. [`AddressPayload`] offers an owned, strongly-typed
+//! value for callers that want to store a decoded payload instead of re-extracting it from an
+//! [`Address`] each time.
+//!
 //! # Example
 //!
 //! ```
@@ -23,7 +34,7 @@
 //! let address = String::from_str(&env, "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC");
 //! let address = Address::from_string(&address);
 //!
-//! if let Some((payload_type, payload)) = address.payload(&env) {
+//! if let Ok((payload_type, payload, muxed_id)) = address.payload(&env) {
 //!     match payload_type {
 //!         AddressPayloadType::ContractHash => {
 //!             // 32-byte contract hash
@@ -31,15 +42,28 @@
 //!         AddressPayloadType::AccountEd25519PublicKey => {
 //!             // 32-byte ed25519 public key
 //!         }
+//!         AddressPayloadType::MuxedAccountEd25519 => {
+//!             // 32-byte ed25519 public key, with muxed_id holding the multiplexing id
+//!         }
+//!         AddressPayloadType::ClaimableBalanceHash => {
+//!             // 32-byte claimable balance hash
+//!         }
+//!         AddressPayloadType::LiquidityPoolHash => {
+//!             // 32-byte liquidity pool hash
+//!         }
 //!     }
 //! }
 //! ```
 
 #![no_std]
-use soroban_sdk::unwrap::UnwrapOptimized;
 use soroban_sdk::xdr::{FromXdr, ToXdr};
 use soroban_sdk::{Address, Bytes, BytesN, Env};
 
+mod address_payload;
+pub mod strkey;
+
+pub use address_payload::AddressPayload;
+
 /// The type of payload contained in an [`Address`].
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum AddressPayloadType {
@@ -47,21 +71,54 @@ pub enum AddressPayloadType {
     AccountEd25519PublicKey,
     /// A contract hash from a contract address (C...).
     ContractHash,
+    /// An Ed25519 public key from a muxed account address (M...).
+    ///
+    /// The multiplexing id that accompanies this key is not part of the 32-byte payload and is
+    /// returned alongside it.
+    MuxedAccountEd25519,
+    /// A hash identifying a claimable balance from a claimable balance address (B...).
+    ClaimableBalanceHash,
+    /// A hash identifying a liquidity pool from a liquidity pool address (L...).
+    LiquidityPoolHash,
+}
+
+/// An error returned when decoding or constructing an [`Address`] payload fails.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AddressPayloadError {
+    /// The XDR for the address was malformed or too short to decode.
+    InvalidXdr,
+    /// The `ScAddress` discriminant was not recognized. This may occur if a new address type
+    /// has been introduced to the network that this version of this library is not aware of.
+    UnknownAddressType,
+    /// The `PublicKey` discriminant inside an account address was not recognized.
+    UnknownPublicKeyType,
+    /// The `ClaimableBalanceID` discriminant inside a claimable balance address was not
+    /// `ClaimableBalanceIdTypeV0`. This may occur if a new claimable balance id type has been
+    /// introduced to the network that this version of this library is not aware of.
+    UnknownClaimableBalanceIdType,
+    /// The payload supplied to [`from_payload`][AddressPayloadExt::from_payload] was not
+    /// exactly 32 bytes.
+    InvalidPayloadLength,
+    /// [`AddressPayloadType::MuxedAccountEd25519`] was supplied to
+    /// [`from_payload`][AddressPayloadExt::from_payload] without a multiplexing id.
+    MissingMuxedId,
 }
 
 /// Extension trait for extracting the 32-byte payload from an [`Address`].
 pub trait AddressPayloadExt {
     /// Extracts the 32-byte payload from the address.
     ///
-    /// Returns the payload type and the raw 32-byte payload:
-    /// - For contract addresses (C...), returns [`AddressPayloadType::ContractHash`]
-    ///   and the 32-byte contract hash.
-    /// - For account addresses (G...), returns [`AddressPayloadType::AccountEd25519PublicKey`]
-    ///   and the 32-byte Ed25519 public key.
+    /// Returns the payload type, the raw 32-byte payload, and (for muxed account addresses
+    /// only) the 64-bit multiplexing id that accompanies the payload:
+    /// - For contract addresses (C...), returns [`AddressPayloadType::ContractHash`],
+    ///   the 32-byte contract hash, and `None`.
+    /// - For account addresses (G...), returns [`AddressPayloadType::AccountEd25519PublicKey`],
+    ///   the 32-byte Ed25519 public key, and `None`.
+    /// - For muxed account addresses (M...), returns [`AddressPayloadType::MuxedAccountEd25519`],
+    ///   the 32-byte Ed25519 public key, and `Some` of the multiplexing id.
     ///
-    /// Returns `None` if the address type is not recognized. This may occur if
-    /// a new address type has been introduced to the network that this version
-    /// of this library is not aware of.
+    /// Returns an error if the XDR cannot be decoded or the address type is not recognized. See
+    /// [`AddressPayloadError`] for the specific failure causes.
     ///
     /// # Example
     ///
@@ -74,22 +131,40 @@ pub trait AddressPayloadExt {
     /// // Contract address (C...)
     /// let address = String::from_str(&env, "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC");
     /// let address = Address::from_string(&address);
-    /// let (payload_type, payload) = address.payload(&env).unwrap();
+    /// let (payload_type, payload, muxed_id) = address.payload(&env).unwrap();
     /// assert_eq!(payload_type, AddressPayloadType::ContractHash);
     /// assert_eq!(payload.len(), 32);
+    /// assert_eq!(muxed_id, None);
     ///
     /// // Account address (G...)
     /// let address = String::from_str(&env, "GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ");
     /// let address = Address::from_string(&address);
-    /// let (payload_type, payload) = address.payload(&env).unwrap();
+    /// let (payload_type, payload, muxed_id) = address.payload(&env).unwrap();
     /// assert_eq!(payload_type, AddressPayloadType::AccountEd25519PublicKey);
     /// assert_eq!(payload.len(), 32);
+    /// assert_eq!(muxed_id, None);
     /// ```
-    fn payload(&self, env: &Env) -> Option<(AddressPayloadType, Bytes)>;
+    fn payload(
+        &self,
+        env: &Env,
+    ) -> Result<(AddressPayloadType, Bytes, Option<u64>), AddressPayloadError>;
+
+    /// Extracts the 32-byte payload from the address, discarding the failure cause.
+    ///
+    /// A thin wrapper around [`payload`][AddressPayloadExt::payload] for callers that only
+    /// care whether decoding succeeded.
+    fn payload_opt(&self, env: &Env) -> Option<(AddressPayloadType, Bytes, Option<u64>)> {
+        self.payload(env).ok()
+    }
 
     /// Constructs an [`Address`] from a payload type and 32-byte payload.
     ///
-    /// This is the inverse of [`payload`][AddressPayloadExt::payload].
+    /// This is the inverse of [`payload`][AddressPayloadExt::payload]. The `muxed_id` argument
+    /// supplies the multiplexing id for [`AddressPayloadType::MuxedAccountEd25519`] and is
+    /// ignored for all other payload types.
+    ///
+    /// Returns an error if `payload` is not exactly 32 bytes, or if
+    /// [`AddressPayloadType::MuxedAccountEd25519`] is supplied without a `muxed_id`.
     ///
     /// # Example
     ///
@@ -104,46 +179,133 @@ pub trait AddressPayloadExt {
     ///     &env,
     ///     0xd7928b72c2703ccfeaf7eb9ff4ef4d504a55a8b979fc9b450ea2c842b4d1ce61
     /// );
-    /// let address = Address::from_payload(&env, AddressPayloadType::ContractHash, &hash);
+    /// let address =
+    ///     Address::from_payload(&env, AddressPayloadType::ContractHash, &hash, None).unwrap();
     /// assert_eq!(
     ///     address.to_string().to_string(),
     ///     "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
     /// );
     /// ```
-    fn from_payload(env: &Env, payload_type: AddressPayloadType, payload: &Bytes) -> Address;
+    fn from_payload(
+        env: &Env,
+        payload_type: AddressPayloadType,
+        payload: &Bytes,
+        muxed_id: Option<u64>,
+    ) -> Result<Address, AddressPayloadError>;
 }
 
-impl AddressPayloadExt for Address {
-    fn payload(&self, env: &Env) -> Option<(AddressPayloadType, Bytes)> {
-        let xdr = self.to_xdr(env);
-        // Skip over ScVal discriminant because we know it is an ScAddress.
-        let xdr = xdr.slice(4..);
-        // Decode ScAddress
-        let addr_type: BytesN<4> = xdr.slice(0..4).try_into().unwrap_optimized();
-        match addr_type.to_array() {
-            // Decode ScAddress::Account
-            [0, 0, 0, 0] => {
-                // Decode PublicKey
-                let public_key_type: BytesN<4> = xdr.slice(4..8).try_into().unwrap_optimized();
-                match public_key_type.to_array() {
-                    // Decode PublicKey::PublicKeyTypeEd25519
-                    [0, 0, 0, 0] => {
-                        let ed25519 = xdr.slice(8..40);
-                        Some((AddressPayloadType::AccountEd25519PublicKey, ed25519))
-                    }
-                    _ => None,
+/// Decodes the 32-byte payload out of the raw XDR for an `ScVal::Address`.
+///
+/// Split out of [`AddressPayloadExt::payload`] so that the decoding logic can be exercised
+/// directly with hand-built XDR, since a real [`Address`] always round-trips through
+/// [`Address::to_xdr`] cleanly and so cannot itself produce malformed input.
+fn decode_payload(
+    xdr: Bytes,
+) -> Result<(AddressPayloadType, Bytes, Option<u64>), AddressPayloadError> {
+    if xdr.len() < 8 {
+        return Err(AddressPayloadError::InvalidXdr);
+    }
+    // Skip over ScVal discriminant because we know it is an ScAddress.
+    let xdr = xdr.slice(4..);
+    // Decode ScAddress
+    let addr_type: BytesN<4> = xdr
+        .slice(0..4)
+        .try_into()
+        .map_err(|_| AddressPayloadError::InvalidXdr)?;
+    match addr_type.to_array() {
+        // Decode ScAddress::Account
+        [0, 0, 0, 0] => {
+            if xdr.len() < 40 {
+                return Err(AddressPayloadError::InvalidXdr);
+            }
+            // Decode PublicKey
+            let public_key_type: BytesN<4> = xdr
+                .slice(4..8)
+                .try_into()
+                .map_err(|_| AddressPayloadError::InvalidXdr)?;
+            match public_key_type.to_array() {
+                // Decode PublicKey::PublicKeyTypeEd25519
+                [0, 0, 0, 0] => {
+                    let ed25519 = xdr.slice(8..40);
+                    Ok((AddressPayloadType::AccountEd25519PublicKey, ed25519, None))
                 }
+                _ => Err(AddressPayloadError::UnknownPublicKeyType),
             }
-            // Decode ScAddress::Contract
-            [0, 0, 0, 1] => {
-                let hash = xdr.slice(4..36);
-                Some((AddressPayloadType::ContractHash, hash))
+        }
+        // Decode ScAddress::Contract
+        [0, 0, 0, 1] => {
+            if xdr.len() < 36 {
+                return Err(AddressPayloadError::InvalidXdr);
+            }
+            let hash = xdr.slice(4..36);
+            Ok((AddressPayloadType::ContractHash, hash, None))
+        }
+        // Decode ScAddress::MuxedAccount
+        [0, 0, 0, 2] => {
+            if xdr.len() < 44 {
+                return Err(AddressPayloadError::InvalidXdr);
+            }
+            // Decode MuxedEd25519Account { id: uint64, ed25519: uint256 }
+            let id: BytesN<8> = xdr
+                .slice(4..12)
+                .try_into()
+                .map_err(|_| AddressPayloadError::InvalidXdr)?;
+            let id = u64::from_be_bytes(id.to_array());
+            let ed25519 = xdr.slice(12..44);
+            Ok((AddressPayloadType::MuxedAccountEd25519, ed25519, Some(id)))
+        }
+        // Decode ScAddress::ClaimableBalance
+        [0, 0, 0, 3] => {
+            if xdr.len() < 40 {
+                return Err(AddressPayloadError::InvalidXdr);
+            }
+            // Decode ClaimableBalanceID
+            let id_type: BytesN<4> = xdr
+                .slice(4..8)
+                .try_into()
+                .map_err(|_| AddressPayloadError::InvalidXdr)?;
+            match id_type.to_array() {
+                // Decode ClaimableBalanceID::ClaimableBalanceIdTypeV0
+                [0, 0, 0, 0] => {
+                    let hash = xdr.slice(8..40);
+                    Ok((AddressPayloadType::ClaimableBalanceHash, hash, None))
+                }
+                _ => Err(AddressPayloadError::UnknownClaimableBalanceIdType),
+            }
+        }
+        // Decode ScAddress::LiquidityPool
+        [0, 0, 0, 4] => {
+            if xdr.len() < 36 {
+                return Err(AddressPayloadError::InvalidXdr);
             }
-            _ => None,
+            let hash = xdr.slice(4..36);
+            Ok((AddressPayloadType::LiquidityPoolHash, hash, None))
         }
+        _ => Err(AddressPayloadError::UnknownAddressType),
+    }
+}
+
+impl AddressPayloadExt for Address {
+    fn payload(
+        &self,
+        env: &Env,
+    ) -> Result<(AddressPayloadType, Bytes, Option<u64>), AddressPayloadError> {
+        decode_payload(self.to_xdr(env))
     }
 
-    fn from_payload(env: &Env, payload_type: AddressPayloadType, payload: &Bytes) -> Address {
+    fn from_payload(
+        env: &Env,
+        payload_type: AddressPayloadType,
+        payload: &Bytes,
+        muxed_id: Option<u64>,
+    ) -> Result<Address, AddressPayloadError> {
+        if payload.len() != 32 {
+            return Err(AddressPayloadError::InvalidPayloadLength);
+        }
+        if payload_type == AddressPayloadType::MuxedAccountEd25519 && muxed_id.is_none() {
+            return Err(AddressPayloadError::MissingMuxedId);
+        }
+
         // Build XDR header based on payload type:
         let header: &[u8] = match payload_type {
             AddressPayloadType::AccountEd25519PublicKey => &[
@@ -155,11 +317,132 @@ impl AddressPayloadExt for Address {
                 0, 0, 0, 18, // ScVal::Address
                 0, 0, 0, 1, // ScAddress::Contract
             ],
+            AddressPayloadType::MuxedAccountEd25519 => &[
+                0, 0, 0, 18, // ScVal::Address
+                0, 0, 0, 2, // ScAddress::MuxedAccount
+            ],
+            AddressPayloadType::ClaimableBalanceHash => &[
+                0, 0, 0, 18, // ScVal::Address
+                0, 0, 0, 3, // ScAddress::ClaimableBalance
+                0, 0, 0, 0, // ClaimableBalanceID::ClaimableBalanceIdTypeV0
+            ],
+            AddressPayloadType::LiquidityPoolHash => &[
+                0, 0, 0, 18, // ScVal::Address
+                0, 0, 0, 4, // ScAddress::LiquidityPool
+            ],
         };
 
         let mut xdr = Bytes::from_slice(env, header);
+        if payload_type == AddressPayloadType::MuxedAccountEd25519 {
+            // Checked above: muxed_id is Some for this payload type.
+            let id = muxed_id.unwrap();
+            xdr.append(&Bytes::from_slice(env, &id.to_be_bytes()));
+        }
         xdr.append(payload);
 
-        Address::from_xdr(env, &xdr).unwrap_optimized()
+        Address::from_xdr(env, &xdr).map_err(|_| AddressPayloadError::InvalidXdr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::EnvTestConfig;
+
+    // `decode_payload` is tested directly with hand-built XDR because a real `Address` always
+    // round-trips through `to_xdr` cleanly, so the XDR it produces can never be too short or
+    // carry an unrecognized discriminant.
+
+    #[test]
+    fn test_decode_payload_invalid_xdr() {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+
+        // Too short to even hold the ScAddress discriminant.
+        let too_short = Bytes::from_slice(&env, &[0, 0, 0, 18, 0, 0]);
+        assert_eq!(
+            decode_payload(too_short).unwrap_err(),
+            AddressPayloadError::InvalidXdr,
+        );
+
+        // Account discriminant present, but truncated before the Ed25519 key.
+        let truncated_account = Bytes::from_slice(
+            &env,
+            &[
+                0, 0, 0, 18, // ScVal::Address
+                0, 0, 0, 0, // ScAddress::Account
+                0, 0, 0, 0, // PublicKey::PublicKeyTypeEd25519
+            ],
+        );
+        assert_eq!(
+            decode_payload(truncated_account).unwrap_err(),
+            AddressPayloadError::InvalidXdr,
+        );
+    }
+
+    #[test]
+    fn test_decode_payload_unknown_address_type() {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+
+        let xdr = Bytes::from_slice(
+            &env,
+            &[
+                0, 0, 0, 18, // ScVal::Address
+                0, 0, 0, 99, // unrecognized ScAddress discriminant
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0,
+            ],
+        );
+        assert_eq!(
+            decode_payload(xdr).unwrap_err(),
+            AddressPayloadError::UnknownAddressType,
+        );
+    }
+
+    #[test]
+    fn test_decode_payload_unknown_public_key_type() {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+
+        let xdr = Bytes::from_slice(
+            &env,
+            &[
+                0, 0, 0, 18, // ScVal::Address
+                0, 0, 0, 0, // ScAddress::Account
+                0, 0, 0, 99, // unrecognized PublicKey discriminant
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0,
+            ],
+        );
+        assert_eq!(
+            decode_payload(xdr).unwrap_err(),
+            AddressPayloadError::UnknownPublicKeyType,
+        );
+    }
+
+    #[test]
+    fn test_decode_payload_unknown_claimable_balance_id_type() {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+
+        let xdr = Bytes::from_slice(
+            &env,
+            &[
+                0, 0, 0, 18, // ScVal::Address
+                0, 0, 0, 3, // ScAddress::ClaimableBalance
+                0, 0, 0, 99, // unrecognized ClaimableBalanceID discriminant
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 0,
+            ],
+        );
+        assert_eq!(
+            decode_payload(xdr).unwrap_err(),
+            AddressPayloadError::UnknownClaimableBalanceIdType,
+        );
     }
 }
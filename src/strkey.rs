@@ -0,0 +1,228 @@
+//! A self-contained Stellar strkey codec.
+//!
+//! Unlike [`AddressPayloadExt`][crate::AddressPayloadExt], these functions do not go through XDR
+//! or require an [`Env`][soroban_sdk::Env]. They encode and decode the
+//! `G.../C.../M.../B.../L...` string form directly from a 32-byte payload, which is useful when
+//! an address needs to be parsed or formatted outside of a contract invocation.
+
+use soroban_sdk::unwrap::UnwrapOptimized;
+
+use crate::AddressPayloadType;
+
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// `version ++ payload ++ crc`, sized for the largest supported payload (muxed account: 1 + 32 +
+/// 8 + 2).
+const MAX_DATA_LEN: usize = 43;
+/// Base32 encoding of [`MAX_DATA_LEN`] bytes, unpadded.
+const MAX_STRKEY_LEN: usize = 69;
+
+/// An encoded strkey string.
+///
+/// Holds the ASCII strkey characters in a fixed-size buffer so that encoding does not require an
+/// allocator. Use [`as_str`][Strkey::as_str] to borrow the encoded string.
+pub struct Strkey {
+    buf: [u8; MAX_STRKEY_LEN],
+    len: usize,
+}
+
+impl Strkey {
+    /// Returns the encoded strkey as a string slice.
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_optimized()
+    }
+}
+
+fn version_byte(payload_type: AddressPayloadType) -> u8 {
+    match payload_type {
+        AddressPayloadType::AccountEd25519PublicKey => 6 << 3,
+        AddressPayloadType::ContractHash => 2 << 3,
+        AddressPayloadType::MuxedAccountEd25519 => 12 << 3,
+        AddressPayloadType::ClaimableBalanceHash => 1 << 3,
+        AddressPayloadType::LiquidityPoolHash => 11 << 3,
+    }
+}
+
+fn payload_type_from_version_byte(version_byte: u8) -> Option<AddressPayloadType> {
+    match version_byte >> 3 {
+        6 => Some(AddressPayloadType::AccountEd25519PublicKey),
+        2 => Some(AddressPayloadType::ContractHash),
+        12 => Some(AddressPayloadType::MuxedAccountEd25519),
+        1 => Some(AddressPayloadType::ClaimableBalanceHash),
+        11 => Some(AddressPayloadType::LiquidityPoolHash),
+        _ => None,
+    }
+}
+
+/// CRC-16/XMODEM: polynomial `0x1021`, initial value `0x0000`, no reflection.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn base32_encode(data: &[u8], out: &mut [u8]) -> usize {
+    let mut bits: u32 = 0;
+    let mut bit_count = 0;
+    let mut out_len = 0;
+    for &byte in data {
+        bits = (bits << 8) | byte as u32;
+        bit_count += 8;
+        while bit_count >= 5 {
+            bit_count -= 5;
+            out[out_len] = ALPHABET[((bits >> bit_count) & 0x1f) as usize];
+            out_len += 1;
+        }
+    }
+    if bit_count > 0 {
+        out[out_len] = ALPHABET[((bits << (5 - bit_count)) & 0x1f) as usize];
+        out_len += 1;
+    }
+    out_len
+}
+
+fn base32_decode(data: &[u8], out: &mut [u8]) -> Option<usize> {
+    let mut bits: u32 = 0;
+    let mut bit_count = 0;
+    let mut out_len = 0;
+    for &c in data {
+        let value = ALPHABET.iter().position(|&a| a == c)? as u32;
+        bits = (bits << 5) | value;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            if out_len >= out.len() {
+                return None;
+            }
+            out[out_len] = ((bits >> bit_count) & 0xff) as u8;
+            out_len += 1;
+        }
+    }
+    Some(out_len)
+}
+
+/// Encodes a payload type and 32-byte payload as a strkey string (e.g. `G...`, `C...`, `M...`).
+///
+/// `muxed_id` supplies the multiplexing id for [`AddressPayloadType::MuxedAccountEd25519`] and is
+/// ignored for all other payload types.
+///
+/// # Example
+///
+/// ```
+/// use soroban_address_payload_ext::{strkey, AddressPayloadType};
+///
+/// let payload = [0xd7, 0x92, 0x8b, 0x72, 0xc2, 0x70, 0x3c, 0xcf, 0xea, 0xf7, 0xeb, 0x9f, 0xf4,
+///     0xef, 0x4d, 0x50, 0x4a, 0x55, 0xa8, 0xb9, 0x79, 0xfc, 0x9b, 0x45, 0x0e, 0xa2, 0xc8, 0x42,
+///     0xb4, 0xd1, 0xce, 0x61];
+/// let encoded = strkey::to_strkey(AddressPayloadType::ContractHash, &payload, None);
+/// assert_eq!(
+///     encoded.as_str(),
+///     "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+/// );
+/// ```
+pub fn to_strkey(
+    payload_type: AddressPayloadType,
+    payload: &[u8; 32],
+    muxed_id: Option<u64>,
+) -> Strkey {
+    let mut data = [0u8; MAX_DATA_LEN];
+    data[0] = version_byte(payload_type);
+    let mut data_len = 1;
+    match payload_type {
+        AddressPayloadType::ClaimableBalanceHash => {
+            // ClaimableBalanceID::ClaimableBalanceIdTypeV0
+            data[data_len] = 0;
+            data_len += 1;
+        }
+        AddressPayloadType::ContractHash
+        | AddressPayloadType::AccountEd25519PublicKey
+        | AddressPayloadType::MuxedAccountEd25519
+        | AddressPayloadType::LiquidityPoolHash => {}
+    }
+    data[data_len..data_len + 32].copy_from_slice(payload);
+    data_len += 32;
+    if payload_type == AddressPayloadType::MuxedAccountEd25519 {
+        // The multiplexing id follows the ed25519 key.
+        let id = muxed_id.unwrap_optimized();
+        data[data_len..data_len + 8].copy_from_slice(&id.to_be_bytes());
+        data_len += 8;
+    }
+    let crc = crc16(&data[..data_len]);
+    data[data_len..data_len + 2].copy_from_slice(&crc.to_le_bytes());
+    data_len += 2;
+
+    let mut buf = [0u8; MAX_STRKEY_LEN];
+    let len = base32_encode(&data[..data_len], &mut buf);
+    Strkey { buf, len }
+}
+
+/// Decodes a strkey string (e.g. `G...`, `C...`, `M...`) into a payload type, 32-byte payload,
+/// and (for muxed account addresses) the multiplexing id.
+///
+/// Returns `None` if the string is not valid base32, the checksum does not match, or the version
+/// byte is not a recognized payload type.
+///
+/// # Example
+///
+/// ```
+/// use soroban_address_payload_ext::{strkey, AddressPayloadType};
+///
+/// let (payload_type, payload, muxed_id) =
+///     strkey::from_strkey("CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC").unwrap();
+/// assert_eq!(payload_type, AddressPayloadType::ContractHash);
+/// assert_eq!(muxed_id, None);
+/// ```
+pub fn from_strkey(s: &str) -> Option<(AddressPayloadType, [u8; 32], Option<u64>)> {
+    let mut data = [0u8; MAX_DATA_LEN];
+    let data_len = base32_decode(s.as_bytes(), &mut data)?;
+    if data_len < 3 {
+        return None;
+    }
+    let (body, crc_bytes) = data[..data_len].split_at(data_len - 2);
+    let expected_crc = u16::from_le_bytes(crc_bytes.try_into().unwrap_optimized());
+    if crc16(body) != expected_crc {
+        return None;
+    }
+
+    let payload_type = payload_type_from_version_byte(body[0])?;
+    let rest = &body[1..];
+    match payload_type {
+        AddressPayloadType::MuxedAccountEd25519 => {
+            if rest.len() != 40 {
+                return None;
+            }
+            let mut payload = [0u8; 32];
+            payload.copy_from_slice(&rest[..32]);
+            let id = u64::from_be_bytes(rest[32..40].try_into().unwrap_optimized());
+            Some((payload_type, payload, Some(id)))
+        }
+        AddressPayloadType::ClaimableBalanceHash => {
+            // ClaimableBalanceID::ClaimableBalanceIdTypeV0
+            if rest.len() != 33 || rest[0] != 0 {
+                return None;
+            }
+            let mut payload = [0u8; 32];
+            payload.copy_from_slice(&rest[1..33]);
+            Some((payload_type, payload, None))
+        }
+        AddressPayloadType::ContractHash
+        | AddressPayloadType::AccountEd25519PublicKey
+        | AddressPayloadType::LiquidityPoolHash => {
+            if rest.len() != 32 {
+                return None;
+            }
+            let mut payload = [0u8; 32];
+            payload.copy_from_slice(rest);
+            Some((payload_type, payload, None))
+        }
+    }
+}
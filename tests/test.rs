@@ -1,5 +1,7 @@
-use soroban_address_payload_ext::{AddressPayloadExt, AddressPayloadType};
-use soroban_sdk::{bytes, testutils::EnvTestConfig, Address, Bytes, Env, String};
+use soroban_address_payload_ext::{
+    strkey, AddressPayload, AddressPayloadError, AddressPayloadExt, AddressPayloadType,
+};
+use soroban_sdk::{bytes, testutils::EnvTestConfig, Address, Bytes, BytesN, Env, String};
 
 #[test]
 fn test_payload() {
@@ -7,8 +9,8 @@ fn test_payload() {
         capture_snapshot_at_drop: false,
     });
 
-    // Test cases: (address, expected_type, expected_payload)
-    let test_cases: [(&str, AddressPayloadType, Bytes); 2] = [
+    // Test cases: (address, expected_type, expected_payload, expected_muxed_id)
+    let test_cases: [(&str, AddressPayloadType, Bytes, Option<u64>); 5] = [
         // Contract address (C...)
         (
             "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
@@ -17,6 +19,7 @@ fn test_payload() {
                 &env,
                 0xd7928b72c2703ccfeaf7eb9ff4ef4d504a55a8b979fc9b450ea2c842b4d1ce61
             ),
+            None,
         ),
         // Account address (G...)
         (
@@ -26,24 +29,202 @@ fn test_payload() {
                 &env,
                 0x899b2840ed5636c56ddc5f14b23975f79f1ba2388d2694e4c56ecdddc960e5ef
             ),
+            None,
+        ),
+        // Muxed account address (M...)
+        (
+            "MCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS66AAAAAAAAAAAFLB46",
+            AddressPayloadType::MuxedAccountEd25519,
+            bytes!(
+                &env,
+                0x899b2840ed5636c56ddc5f14b23975f79f1ba2388d2694e4c56ecdddc960e5ef
+            ),
+            Some(42),
+        ),
+        // Claimable balance address (B...)
+        (
+            "BAAITGZIIDWVMNWFNXOF6FFSHF27PHY3UI4I2JUU4TCW5TO5ZFQOL3YFPU",
+            AddressPayloadType::ClaimableBalanceHash,
+            bytes!(
+                &env,
+                0x899b2840ed5636c56ddc5f14b23975f79f1ba2388d2694e4c56ecdddc960e5ef
+            ),
+            None,
+        ),
+        // Liquidity pool address (L...)
+        (
+            "LCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS66BGN",
+            AddressPayloadType::LiquidityPoolHash,
+            bytes!(
+                &env,
+                0x899b2840ed5636c56ddc5f14b23975f79f1ba2388d2694e4c56ecdddc960e5ef
+            ),
+            None,
         ),
     ];
 
-    for (address, payload_type, payload) in test_cases {
+    for (address, payload_type, payload, muxed_id) in test_cases {
         let address = String::from_str(&env, address);
         let address = Address::from_string(&address);
 
         // Test payload:
         {
-            let (actual_payload_type, actual_payload) = address.payload(&env).unwrap();
+            let (actual_payload_type, actual_payload, actual_muxed_id) =
+                address.payload(&env).unwrap();
             assert_eq!(actual_payload_type, payload_type);
             assert_eq!(actual_payload, payload);
+            assert_eq!(actual_muxed_id, muxed_id);
         }
 
         // Test from_payload:
         {
-            let actual_address = Address::from_payload(&env, payload_type, &payload);
+            let actual_address =
+                Address::from_payload(&env, payload_type, &payload, muxed_id).unwrap();
             assert_eq!(actual_address, address);
         }
     }
 }
+
+#[test]
+fn test_payload_errors() {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    // Wrong-sized payload is rejected instead of panicking.
+    let too_short = bytes!(&env, 0x0102);
+    assert_eq!(
+        Address::from_payload(&env, AddressPayloadType::ContractHash, &too_short, None)
+            .unwrap_err(),
+        AddressPayloadError::InvalidPayloadLength,
+    );
+
+    // A muxed account requires a multiplexing id.
+    let key = bytes!(
+        &env,
+        0x899b2840ed5636c56ddc5f14b23975f79f1ba2388d2694e4c56ecdddc960e5ef
+    );
+    assert_eq!(
+        Address::from_payload(&env, AddressPayloadType::MuxedAccountEd25519, &key, None)
+            .unwrap_err(),
+        AddressPayloadError::MissingMuxedId,
+    );
+}
+
+#[test]
+fn test_strkey() {
+    // Test cases: (address, payload_type, payload, muxed_id)
+    let test_cases: [(&str, AddressPayloadType, [u8; 32], Option<u64>); 5] = [
+        // Contract address (C...)
+        (
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
+            AddressPayloadType::ContractHash,
+            [
+                0xd7, 0x92, 0x8b, 0x72, 0xc2, 0x70, 0x3c, 0xcf, 0xea, 0xf7, 0xeb, 0x9f, 0xf4, 0xef,
+                0x4d, 0x50, 0x4a, 0x55, 0xa8, 0xb9, 0x79, 0xfc, 0x9b, 0x45, 0x0e, 0xa2, 0xc8, 0x42,
+                0xb4, 0xd1, 0xce, 0x61,
+            ],
+            None,
+        ),
+        // Account address (G...)
+        (
+            "GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ",
+            AddressPayloadType::AccountEd25519PublicKey,
+            [
+                0x89, 0x9b, 0x28, 0x40, 0xed, 0x56, 0x36, 0xc5, 0x6d, 0xdc, 0x5f, 0x14, 0xb2, 0x39,
+                0x75, 0xf7, 0x9f, 0x1b, 0xa2, 0x38, 0x8d, 0x26, 0x94, 0xe4, 0xc5, 0x6e, 0xcd, 0xdd,
+                0xc9, 0x60, 0xe5, 0xef,
+            ],
+            None,
+        ),
+        // Muxed account address (M...)
+        (
+            "MCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS66AAAAAAAAAAAFLB46",
+            AddressPayloadType::MuxedAccountEd25519,
+            [
+                0x89, 0x9b, 0x28, 0x40, 0xed, 0x56, 0x36, 0xc5, 0x6d, 0xdc, 0x5f, 0x14, 0xb2, 0x39,
+                0x75, 0xf7, 0x9f, 0x1b, 0xa2, 0x38, 0x8d, 0x26, 0x94, 0xe4, 0xc5, 0x6e, 0xcd, 0xdd,
+                0xc9, 0x60, 0xe5, 0xef,
+            ],
+            Some(42),
+        ),
+        // Claimable balance address (B...)
+        (
+            "BAAITGZIIDWVMNWFNXOF6FFSHF27PHY3UI4I2JUU4TCW5TO5ZFQOL3YFPU",
+            AddressPayloadType::ClaimableBalanceHash,
+            [
+                0x89, 0x9b, 0x28, 0x40, 0xed, 0x56, 0x36, 0xc5, 0x6d, 0xdc, 0x5f, 0x14, 0xb2, 0x39,
+                0x75, 0xf7, 0x9f, 0x1b, 0xa2, 0x38, 0x8d, 0x26, 0x94, 0xe4, 0xc5, 0x6e, 0xcd, 0xdd,
+                0xc9, 0x60, 0xe5, 0xef,
+            ],
+            None,
+        ),
+        // Liquidity pool address (L...)
+        (
+            "LCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS66BGN",
+            AddressPayloadType::LiquidityPoolHash,
+            [
+                0x89, 0x9b, 0x28, 0x40, 0xed, 0x56, 0x36, 0xc5, 0x6d, 0xdc, 0x5f, 0x14, 0xb2, 0x39,
+                0x75, 0xf7, 0x9f, 0x1b, 0xa2, 0x38, 0x8d, 0x26, 0x94, 0xe4, 0xc5, 0x6e, 0xcd, 0xdd,
+                0xc9, 0x60, 0xe5, 0xef,
+            ],
+            None,
+        ),
+    ];
+
+    for (address, payload_type, payload, muxed_id) in test_cases {
+        // Test from_strkey:
+        {
+            let (actual_payload_type, actual_payload, actual_muxed_id) =
+                strkey::from_strkey(address).unwrap();
+            assert_eq!(actual_payload_type, payload_type);
+            assert_eq!(actual_payload, payload);
+            assert_eq!(actual_muxed_id, muxed_id);
+        }
+
+        // Test to_strkey:
+        {
+            let encoded = strkey::to_strkey(payload_type, &payload, muxed_id);
+            assert_eq!(encoded.as_str(), address);
+        }
+    }
+
+    // Invalid checksum is rejected.
+    assert!(
+        strkey::from_strkey("CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYST").is_none()
+    );
+}
+
+#[test]
+fn test_address_payload() {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+
+    let key = [
+        0x89, 0x9b, 0x28, 0x40, 0xed, 0x56, 0x36, 0xc5, 0x6d, 0xdc, 0x5f, 0x14, 0xb2, 0x39, 0x75,
+        0xf7, 0x9f, 0x1b, 0xa2, 0x38, 0x8d, 0x26, 0x94, 0xe4, 0xc5, 0x6e, 0xcd, 0xdd, 0xc9, 0x60,
+        0xe5, 0xef,
+    ];
+
+    // Round trip through an Address:
+    let address_payload =
+        AddressPayload::from_array(&env, AddressPayloadType::MuxedAccountEd25519, key, Some(42))
+            .unwrap();
+    let address: Address = address_payload.clone().into();
+    let roundtripped: AddressPayload = address.try_into().unwrap();
+    assert_eq!(roundtripped, address_payload);
+    assert_eq!(
+        roundtripped.payload_type(),
+        AddressPayloadType::MuxedAccountEd25519
+    );
+    assert_eq!(roundtripped.muxed_id(), Some(42));
+    assert_eq!(roundtripped.payload(), BytesN::from_array(&env, &key));
+
+    // A muxed payload requires a muxed id.
+    assert_eq!(
+        AddressPayload::from_array(&env, AddressPayloadType::MuxedAccountEd25519, key, None)
+            .unwrap_err(),
+        AddressPayloadError::MissingMuxedId,
+    );
+}